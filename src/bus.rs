@@ -0,0 +1,93 @@
+use crate::Address;
+
+/// T-cycles in one M-cycle: the DMG's CPU and bus run at a quarter of the
+/// 4.194304 MHz master clock, so every bus access costs a whole number of
+/// M-cycles, each 4 T-cycles long.
+pub(crate) const CYCLES_PER_M_CYCLE: u8 = 4;
+
+/// A bus the CPU drives one access at a time, with each access reporting
+/// the T-cycles it took. Implementors range from a flat RAM used in tests
+/// to the full mapped [`MemoryMap`](crate::memory::MemoryMap); keeping the
+/// trait object-safe lets `Motherboard` hold whichever one it's given
+/// behind a `dyn MemoryInterface`.
+pub(crate) trait MemoryInterface {
+    /// Reads the byte at `address`, returning it together with the
+    /// T-cycles the access consumed.
+    fn read_byte(&mut self, address: Address) -> (u8, u8);
+
+    /// Writes `data` to `address`, returning the T-cycles the access
+    /// consumed.
+    fn write_byte(&mut self, address: Address, data: u8) -> u8;
+
+    /// Reads IE (0xFFFF) without bus timing. Real hardware latches the
+    /// interrupt-enable bits internally each cycle rather than issuing a
+    /// bus access, so checking for pending interrupts shouldn't itself
+    /// cost CPU time.
+    fn interrupt_enable(&self) -> u8;
+
+    /// Reads IF (0xFF0F) without bus timing; see [`interrupt_enable`].
+    ///
+    /// [`interrupt_enable`]: MemoryInterface::interrupt_enable
+    fn interrupt_flag(&self) -> u8;
+
+    /// Overwrites IF (0xFF0F) without bus timing, e.g. to clear the bit of
+    /// an interrupt the CPU is about to service.
+    fn set_interrupt_flag(&mut self, flag: u8);
+
+    /// Advances any clocked peripherals (currently just the timer) by
+    /// `cycles` T-cycles, requesting interrupts as they come due.
+    fn tick(&mut self, cycles: u8);
+
+    /// Reads `address` without bus timing or side effects, for tooling
+    /// (disassembler, debugger memory inspector) that shouldn't itself
+    /// advance the clock.
+    fn peek_byte(&self, address: Address) -> u8;
+
+    /// Writes `address` without bus timing, for debugger memory edits.
+    fn poke_byte(&mut self, address: Address, data: u8);
+}
+
+/// A flat 64 KiB RAM with no address decoding or mapper, for tests that
+/// want a bus without the real `MemoryMap`'s cartridge/MBC behaviour.
+#[derive(Clone)]
+pub(crate) struct FlatRam(pub(crate) [u8; 0x10000]);
+
+impl Default for FlatRam {
+    fn default() -> Self {
+        Self([0; 0x10000])
+    }
+}
+
+impl MemoryInterface for FlatRam {
+    fn read_byte(&mut self, address: Address) -> (u8, u8) {
+        (self.0[address as usize], CYCLES_PER_M_CYCLE)
+    }
+
+    fn write_byte(&mut self, address: Address, data: u8) -> u8 {
+        self.0[address as usize] = data;
+        CYCLES_PER_M_CYCLE
+    }
+
+    fn interrupt_enable(&self) -> u8 {
+        self.0[0xFFFF]
+    }
+
+    fn interrupt_flag(&self) -> u8 {
+        self.0[0xFF0F]
+    }
+
+    fn set_interrupt_flag(&mut self, flag: u8) {
+        self.0[0xFF0F] = flag;
+    }
+
+    // No peripherals are modeled on a flat RAM; nothing to advance.
+    fn tick(&mut self, _cycles: u8) {}
+
+    fn peek_byte(&self, address: Address) -> u8 {
+        self.0[address as usize]
+    }
+
+    fn poke_byte(&mut self, address: Address, data: u8) {
+        self.0[address as usize] = data;
+    }
+}
@@ -1,34 +1,43 @@
+mod bus;
+mod memory;
+mod timer;
+
+use bus::MemoryInterface;
+use memory::MemoryMap;
+
 type Address = u16;
 
-#[derive(Default)]
 struct Motherboard {
     cpu: Core::SM83,
-    memory_map: MemoryMap,
-}
-
-struct MemoryMap {
-    work_ram: [u8; 0x2000],
+    memory_map: Box<dyn MemoryInterface>,
 }
 
-impl Default for MemoryMap {
+impl Default for Motherboard {
     fn default() -> Self {
         Self {
-            work_ram: [0; 0x2000],
+            cpu: Core::SM83::default(),
+            memory_map: Box::new(MemoryMap::default()),
         }
     }
 }
 
-impl MemoryMap {
-    fn read_byte_at(&self, address: Address) -> u8 {
-        self.work_ram[address as usize]
-    }
-
-    fn write_byte_at(&mut self, address: Address, data: u8) {
-        self.work_ram[address as usize] = data;
+impl Motherboard {
+    /// Builds a motherboard around `rom`, selecting a mapper from its
+    /// cartridge header. When `boot_rom` is given and `skip_boot_rom` is
+    /// false, the CPU starts at `0x0000` with the boot ROM mapped in;
+    /// otherwise it starts post-boot at `0x0100`.
+    pub(crate) fn new(rom: Vec<u8>, boot_rom: Option<[u8; 0x100]>, skip_boot_rom: bool) -> Self {
+        let memory_map = MemoryMap::load_rom(rom, boot_rom, skip_boot_rom);
+        let pc = if memory_map.boot_rom_mapped() { 0x0000 } else { 0x0100 };
+        Self {
+            cpu: Core::SM83::new(pc),
+            memory_map: Box::new(memory_map),
+        }
     }
 }
 
 mod Core {
+    use crate::bus::{MemoryInterface, CYCLES_PER_M_CYCLE};
     use crate::{Address, Motherboard};
 
     use bitflags::bitflags;
@@ -42,8 +51,11 @@ mod Core {
         }
     }
 
+    /// `pub(crate)` rather than private like the rest of this module's
+    /// decode-only helper types: [`Debugger`]'s register accessors take
+    /// these, and `Debugger` is meant to be driven from outside `Core`.
     #[derive(Clone, Copy)]
-    enum Reg8 {
+    pub(crate) enum Reg8 {
         A,
         F,
         B,
@@ -55,11 +67,103 @@ mod Core {
     }
 
     #[derive(Clone, Copy)]
-    enum Reg16 {
+    pub(crate) enum Reg16 {
         AF,
         BC,
         DE,
         HL,
+        SP,
+    }
+
+    /// An 8-bit register or `(HL)`, as selected by the 3-bit `r[y]`/`r[z]`
+    /// sub-fields of the opcode.
+    #[derive(Clone, Copy)]
+    enum R8 {
+        Reg(Reg8),
+        IndirectHl,
+    }
+
+    /// `(rp)` for one of the 16-bit register pairs.
+    #[derive(Clone, Copy)]
+    struct Indirect(Reg16);
+
+    /// `0xFF00 + n` for an immediate offset, or `0xFF00 + C`.
+    #[derive(Clone, Copy)]
+    enum ZeroPage {
+        Immediate,
+        C,
+    }
+
+    /// `(a16)` as an 8-bit operand, for `LD A,(a16)` / `LD (a16),A`.
+    #[derive(Clone, Copy)]
+    struct Direct8;
+
+    /// `(a16)` as a 16-bit destination, for `LD (a16),SP`.
+    #[derive(Clone, Copy)]
+    struct Direct16;
+
+    #[derive(Clone, Copy)]
+    enum Condition {
+        NotZero,
+        Zero,
+        NotCarry,
+        Carry,
+    }
+
+    /// `SM83`'s run state, analogous to a processor's fetch/halt/stop cycle.
+    #[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+    enum State {
+        #[default]
+        Running,
+        /// Waiting for any enabled interrupt; woken even with IME cleared.
+        Halted,
+        /// Waiting for a joypad press (modeled here as any pending interrupt,
+        /// since the joypad peripheral isn't implemented yet).
+        Stopped,
+    }
+
+    /// The five DMG interrupt sources, most to least prioritized — ties
+    /// between pending interrupts are broken in this order.
+    #[derive(Clone, Copy)]
+    enum Interrupt {
+        VBlank,
+        LcdStat,
+        Timer,
+        Serial,
+        Joypad,
+    }
+
+    impl Interrupt {
+        const ALL: [Interrupt; 5] = [
+            Interrupt::VBlank,
+            Interrupt::LcdStat,
+            Interrupt::Timer,
+            Interrupt::Serial,
+            Interrupt::Joypad,
+        ];
+
+        /// Bit position within IE (0xFFFF) / IF (0xFF0F).
+        fn bit(self) -> u8 {
+            match self {
+                Interrupt::VBlank => 0,
+                Interrupt::LcdStat => 1,
+                Interrupt::Timer => 2,
+                Interrupt::Serial => 3,
+                Interrupt::Joypad => 4,
+            }
+        }
+
+        /// Fixed dispatch address the CPU jumps to when servicing this
+        /// interrupt (after pushing `pc`).
+        fn vector(self) -> Address {
+            match self {
+                Interrupt::VBlank => 0x0040,
+                Interrupt::LcdStat => 0x0048,
+                Interrupt::Timer => 0x0050,
+                Interrupt::Serial => 0x0058,
+                Interrupt::Joypad => 0x0060,
+            }
+        }
     }
 
     trait In8<S>
@@ -73,12 +177,29 @@ mod Core {
     where
         D: Copy,
     {
-        fn write(&mut self, md: &mut Motherboard, destination: D, byte: u8);
+        fn write(&mut self, mb: &mut Motherboard, destination: D, byte: u8);
+    }
+
+    trait In16<S>
+    where
+        S: Copy,
+    {
+        fn read16(&mut self, mb: &mut Motherboard, source: S) -> u16;
+    }
+
+    trait Out16<D>
+    where
+        D: Copy,
+    {
+        fn write16(&mut self, mb: &mut Motherboard, destination: D, word: u16);
     }
 
     #[derive(Clone, Copy)]
     struct Immediate8;
 
+    #[derive(Clone, Copy)]
+    struct Immediate16;
+
     #[derive(Default)]
     struct RegisterFile {
         a: u8,
@@ -116,6 +237,7 @@ mod Core {
                     let l: u16 = self.l.into();
                     h << 8 | l
                 }
+                Reg16::SP => self.sp,
             }
         }
 
@@ -137,6 +259,7 @@ mod Core {
                     self.h = (word >> 8) as u8;
                     self.l = word as u8;
                 }
+                Reg16::SP => self.sp = word,
             }
         }
     }
@@ -148,7 +271,7 @@ mod Core {
     }
 
     impl In8<Reg8> for SM83 {
-        fn read(&mut self, mb: &mut Motherboard, source: Reg8) -> u8 {
+        fn read(&mut self, _mb: &mut Motherboard, source: Reg8) -> u8 {
             match source {
                 Reg8::A => self.registers.a,
                 Reg8::F => self.registers.f.bits,
@@ -162,37 +285,1511 @@ mod Core {
         }
     }
 
+    impl Out8<Reg8> for SM83 {
+        fn write(&mut self, _mb: &mut Motherboard, destination: Reg8, byte: u8) {
+            match destination {
+                Reg8::A => self.registers.a = byte,
+                // The low nibble of F is hardwired to zero.
+                Reg8::F => self.registers.f = Flags::from_bits_truncate(byte),
+                Reg8::B => self.registers.b = byte,
+                Reg8::C => self.registers.c = byte,
+                Reg8::D => self.registers.d = byte,
+                Reg8::E => self.registers.e = byte,
+                Reg8::H => self.registers.h = byte,
+                Reg8::L => self.registers.l = byte,
+            }
+        }
+    }
+
+    impl In8<R8> for SM83 {
+        fn read(&mut self, mb: &mut Motherboard, source: R8) -> u8 {
+            match source {
+                R8::Reg(reg) => self.read(mb, reg),
+                R8::IndirectHl => self.read(mb, Indirect(Reg16::HL)),
+            }
+        }
+    }
+
+    impl Out8<R8> for SM83 {
+        fn write(&mut self, mb: &mut Motherboard, destination: R8, byte: u8) {
+            match destination {
+                R8::Reg(reg) => self.write(mb, reg, byte),
+                R8::IndirectHl => self.write(mb, Indirect(Reg16::HL), byte),
+            }
+        }
+    }
+
+    impl In8<Indirect> for SM83 {
+        fn read(&mut self, mb: &mut Motherboard, source: Indirect) -> u8 {
+            let address = self.registers.read16(source.0);
+            self.read_byte(mb, address)
+        }
+    }
+
+    impl Out8<Indirect> for SM83 {
+        fn write(&mut self, mb: &mut Motherboard, destination: Indirect, byte: u8) {
+            let address = self.registers.read16(destination.0);
+            self.write_byte(mb, address, byte);
+        }
+    }
+
+    impl In8<ZeroPage> for SM83 {
+        fn read(&mut self, mb: &mut Motherboard, source: ZeroPage) -> u8 {
+            let offset = match source {
+                ZeroPage::Immediate => self.fetch_byte(mb),
+                ZeroPage::C => self.registers.c,
+            };
+            self.read_byte(mb, 0xFF00 + offset as u16)
+        }
+    }
+
+    impl Out8<ZeroPage> for SM83 {
+        fn write(&mut self, mb: &mut Motherboard, destination: ZeroPage, byte: u8) {
+            let offset = match destination {
+                ZeroPage::Immediate => self.fetch_byte(mb),
+                ZeroPage::C => self.registers.c,
+            };
+            self.write_byte(mb, 0xFF00 + offset as u16, byte);
+        }
+    }
+
+    impl In8<Direct8> for SM83 {
+        fn read(&mut self, mb: &mut Motherboard, _: Direct8) -> u8 {
+            let address = self.fetch_word(mb);
+            self.read_byte(mb, address)
+        }
+    }
+
+    impl Out8<Direct8> for SM83 {
+        fn write(&mut self, mb: &mut Motherboard, _: Direct8, byte: u8) {
+            let address = self.fetch_word(mb);
+            self.write_byte(mb, address, byte);
+        }
+    }
+
+    impl In16<Immediate16> for SM83 {
+        fn read16(&mut self, mb: &mut Motherboard, _: Immediate16) -> u16 {
+            self.fetch_word(mb)
+        }
+    }
+
+    impl In16<Reg16> for SM83 {
+        fn read16(&mut self, _mb: &mut Motherboard, source: Reg16) -> u16 {
+            self.registers.read16(source)
+        }
+    }
+
+    impl Out16<Reg16> for SM83 {
+        fn write16(&mut self, _mb: &mut Motherboard, destination: Reg16, word: u16) {
+            self.registers.write16(destination, word)
+        }
+    }
+
+    impl Out16<Direct16> for SM83 {
+        fn write16(&mut self, mb: &mut Motherboard, _: Direct16, word: u16) {
+            let address = self.fetch_word(mb);
+            self.write_byte(mb, address, word as u8);
+            self.write_byte(mb, address.wrapping_add(1), (word >> 8) as u8);
+        }
+    }
+
+    /// `r[index]` for the 3-bit register sub-field shared by the main table
+    /// and the `0xCB` table: `B,C,D,E,H,L,(HL),A`.
+    fn decode_r8(index: u8) -> R8 {
+        match index {
+            0 => R8::Reg(Reg8::B),
+            1 => R8::Reg(Reg8::C),
+            2 => R8::Reg(Reg8::D),
+            3 => R8::Reg(Reg8::E),
+            4 => R8::Reg(Reg8::H),
+            5 => R8::Reg(Reg8::L),
+            6 => R8::IndirectHl,
+            7 => R8::Reg(Reg8::A),
+            _ => unreachable!("3-bit register index out of range"),
+        }
+    }
+
+    /// `rp[p]` for the 2-bit register-pair sub-field: `BC,DE,HL,SP`.
+    fn decode_rp(p: u8) -> Reg16 {
+        match p {
+            0 => Reg16::BC,
+            1 => Reg16::DE,
+            2 => Reg16::HL,
+            3 => Reg16::SP,
+            _ => unreachable!("2-bit register-pair index out of range"),
+        }
+    }
+
+    /// `rp2[p]`, used by `PUSH`/`POP`: `BC,DE,HL,AF`.
+    fn decode_rp2_stack(p: u8) -> Reg16 {
+        match p {
+            0 => Reg16::BC,
+            1 => Reg16::DE,
+            2 => Reg16::HL,
+            3 => Reg16::AF,
+            _ => unreachable!("2-bit register-pair index out of range"),
+        }
+    }
+
+    /// `cc[y]` for the 2-bit condition sub-field: `NZ,Z,NC,C`.
+    fn decode_condition(index: u8) -> Condition {
+        match index {
+            0 => Condition::NotZero,
+            1 => Condition::Zero,
+            2 => Condition::NotCarry,
+            3 => Condition::Carry,
+            _ => unreachable!("2-bit condition index out of range"),
+        }
+    }
+
     #[derive(Default)]
     pub struct SM83 {
         registers: RegisterFile,
+        /// Interrupt master enable.
+        ime: bool,
+        state: State,
+        /// Instructions left before a pending `EI` takes effect: `EI` sets
+        /// this to 2, and it's decremented once per completed instruction
+        /// (including `EI` itself), so `ime` is only set once the
+        /// instruction *after* `EI` has finished — matching real hardware's
+        /// one-instruction-delayed enable.
+        ei_delay: u8,
+        /// Set when `HALT` is executed with IME clear and an interrupt
+        /// already pending: the next opcode fetch replays the same byte
+        /// instead of advancing `pc`, reproducing the hardware HALT bug.
+        halt_bug: bool,
+        /// Running total of T-cycles elapsed since reset.
+        cycles: u64,
     }
 
     impl SM83 {
-        fn fetch_decode_execute(&mut self, mb: &Motherboard) {
+        /// Builds a CPU with all registers zeroed except `pc`, which is set
+        /// to `pc` (`0x0000` to start in the boot ROM, `0x0100` to start
+        /// post-boot).
+        pub(crate) fn new(pc: Address) -> Self {
+            Self {
+                registers: RegisterFile {
+                    pc,
+                    ..RegisterFile::default()
+                },
+                ..Self::default()
+            }
+        }
+
+        /// Running total of T-cycles elapsed since reset.
+        pub(crate) fn cycles(&self) -> u64 {
+            self.cycles
+        }
+
+        /// Executes one instruction (or, if halted, idles for one M-cycle)
+        /// and returns the T-cycles it consumed, so callers can tick a
+        /// PPU/timer in lockstep.
+        pub(crate) fn step(&mut self, mb: &mut Motherboard) -> u8 {
+            let before = self.cycles;
+            self.fetch_decode_execute(mb);
+            let elapsed = (self.cycles - before) as u8;
+            mb.memory_map.tick(elapsed);
+            elapsed
+        }
+
+        fn read_byte(&mut self, mb: &mut Motherboard, address: Address) -> u8 {
+            let (byte, cycles) = mb.memory_map.read_byte(address);
+            self.cycles += cycles as u64;
+            byte
+        }
+
+        fn write_byte(&mut self, mb: &mut Motherboard, address: Address, data: u8) {
+            let cycles = mb.memory_map.write_byte(address, data);
+            self.cycles += cycles as u64;
+        }
+
+        /// Charges one M-cycle that isn't a bus access — e.g. the cycle a
+        /// taken jump spends overwriting `pc`, or PUSH/CALL/RST's cycle
+        /// decrementing `sp` before the first write. Real SM83 hardware
+        /// spends these even though no read or write happens on the bus.
+        fn internal_cycle(&mut self) {
+            self.cycles += CYCLES_PER_M_CYCLE as u64;
+        }
+
+        fn fetch_decode_execute(&mut self, mb: &mut Motherboard) {
+            if self.service_pending_interrupt(mb) {
+                return;
+            }
+
+            if self.state != State::Running {
+                self.cycles += CYCLES_PER_M_CYCLE as u64;
+                return;
+            }
+
             let address = self.registers.pc;
-            let opcode = mb.memory_map.read_byte_at(address);
+            let opcode = self.read_byte(mb, address);
+            if self.halt_bug {
+                self.halt_bug = false;
+            } else {
+                self.registers.pc = address.wrapping_add(1);
+            }
             self.decode_execute(mb, opcode);
-            self.registers.pc = address.wrapping_add(1);
+
+            if self.ei_delay > 0 {
+                self.ei_delay -= 1;
+                if self.ei_delay == 0 {
+                    self.ime = true;
+                }
+            }
         }
 
-        fn decode_execute(&self, mb: &Motherboard, opcode: u8) {
-            todo!()
+        /// If IME is set and an enabled interrupt is pending, pushes `pc`,
+        /// clears the interrupt's IF bit, clears IME and jumps to the
+        /// interrupt's fixed vector, returning `true` so the caller doesn't
+        /// also fetch an opcode this step. Either way, a pending enabled
+        /// interrupt wakes the CPU from `Halted`/`Stopped`, even with IME
+        /// clear — HALT/STOP just stop being serviced, not observed.
+        fn service_pending_interrupt(&mut self, mb: &mut Motherboard) -> bool {
+            let pending = mb.memory_map.interrupt_enable() & mb.memory_map.interrupt_flag() & 0x1F;
+            if pending == 0 {
+                return false;
+            }
+
+            if self.state != State::Running {
+                self.state = State::Running;
+            }
+
+            if !self.ime {
+                return false;
+            }
+
+            let interrupt = Interrupt::ALL
+                .into_iter()
+                .find(|interrupt| pending & (1 << interrupt.bit()) != 0)
+                .expect("pending != 0 implies some bit is set");
+
+            self.ime = false;
+            let flag = mb.memory_map.interrupt_flag();
+            mb.memory_map
+                .set_interrupt_flag(flag & !(1 << interrupt.bit()));
+            self.push(mb, self.registers.pc);
+            self.registers.pc = interrupt.vector();
+            // Dispatch takes 5 M-cycles total; `push` above already charges
+            // 3 (its internal decrement-sp cycle plus its two writes), so
+            // only the remaining 2 are added here.
+            self.cycles += 2 * CYCLES_PER_M_CYCLE as u64;
+            true
+        }
+
+        /// `HALT`: suspends fetch/execute until an enabled interrupt is
+        /// pending, unless IME is clear and one already is — in which case
+        /// the CPU doesn't actually halt, but triggers the HALT bug instead.
+        fn halt(&mut self, mb: &mut Motherboard) {
+            let pending = mb.memory_map.interrupt_enable() & mb.memory_map.interrupt_flag() & 0x1F;
+            if !self.ime && pending != 0 {
+                self.halt_bug = true;
+            } else {
+                self.state = State::Halted;
+            }
+        }
+
+        /// Decodes `opcode` using the standard `xxyyyzzz` bit-field split
+        /// (`x = opcode[7:6]`, `y = opcode[5:3]`, `z = opcode[2:0]`,
+        /// `p = y[2:1]`, `q = y[0]`) and dispatches to one of the four main
+        /// blocks.
+        fn decode_execute(&mut self, mb: &mut Motherboard, opcode: u8) {
+            let x = opcode >> 6;
+            let y = (opcode >> 3) & 0x07;
+            let z = opcode & 0x07;
+            let p = y >> 1;
+            let q = y & 0x01;
+
+            match x {
+                0 => self.execute_block0(mb, y, z, p, q),
+                1 => self.execute_block1(mb, y, z),
+                2 => self.execute_block2(mb, y, z),
+                3 => self.execute_block3(mb, y, z, p, q),
+                _ => unreachable!("2-bit block index out of range"),
+            }
+        }
+
+        fn execute_block0(&mut self, mb: &mut Motherboard, y: u8, z: u8, p: u8, q: u8) {
+            match z {
+                0 => match y {
+                    0 => {}
+                    1 => self.ld16(mb, Direct16, Reg16::SP),
+                    2 => {
+                        self.state = State::Stopped;
+                        self.fetch_byte(mb);
+                    }
+                    3 => self.jr(mb),
+                    _ => self.jr_if(mb, decode_condition(y - 4)),
+                },
+                1 => {
+                    let rp = decode_rp(p);
+                    if q == 0 {
+                        self.ld16(mb, rp, Immediate16);
+                    } else {
+                        self.add_hl(rp);
+                    }
+                }
+                2 => {
+                    if q == 0 {
+                        self.ld_indirect_a(mb, p);
+                    } else {
+                        self.ld_a_indirect(mb, p);
+                    }
+                }
+                3 => {
+                    let rp = decode_rp(p);
+                    if q == 0 {
+                        self.inc16(rp);
+                    } else {
+                        self.dec16(rp);
+                    }
+                }
+                4 => {
+                    let operand = decode_r8(y);
+                    self.inc8(mb, operand);
+                }
+                5 => {
+                    let operand = decode_r8(y);
+                    self.dec8(mb, operand);
+                }
+                6 => {
+                    let operand = decode_r8(y);
+                    self.ld(mb, operand, Immediate8);
+                }
+                7 => match y {
+                    0 => self.rlca(),
+                    1 => self.rrca(),
+                    2 => self.rla(),
+                    3 => self.rra(),
+                    4 => self.daa(),
+                    5 => self.cpl(),
+                    6 => self.scf(),
+                    7 => self.ccf(),
+                    _ => unreachable!("3-bit y index out of range"),
+                },
+                _ => unreachable!("3-bit z index out of range"),
+            }
+        }
+
+        fn execute_block1(&mut self, mb: &mut Motherboard, y: u8, z: u8) {
+            if y == 6 && z == 6 {
+                self.halt(mb);
+                return;
+            }
+            let destination = decode_r8(y);
+            let source = decode_r8(z);
+            self.ld(mb, destination, source);
+        }
+
+        fn execute_block2(&mut self, mb: &mut Motherboard, y: u8, z: u8) {
+            let source = decode_r8(z);
+            self.alu(mb, y, source);
+        }
+
+        fn execute_block3(&mut self, mb: &mut Motherboard, y: u8, z: u8, p: u8, q: u8) {
+            match z {
+                0 => match y {
+                    0..=3 => self.ret_if(mb, decode_condition(y)),
+                    4 => {
+                        let a = self.registers.a;
+                        self.write(mb, ZeroPage::Immediate, a);
+                    }
+                    5 => self.add_sp_r8(mb),
+                    6 => self.registers.a = self.read(mb, ZeroPage::Immediate),
+                    7 => self.ld_hl_sp_plus_r8(mb),
+                    _ => unreachable!("3-bit y index out of range"),
+                },
+                1 => {
+                    if q == 0 {
+                        let rp = decode_rp2_stack(p);
+                        let word = self.pop(mb);
+                        self.registers.write16(rp, word);
+                    } else {
+                        match p {
+                            0 => self.ret(mb),
+                            1 => {
+                                self.ret(mb);
+                                self.ime = true;
+                                self.ei_delay = 0;
+                            }
+                            2 => self.registers.pc = self.registers.read16(Reg16::HL),
+                            3 => {
+                                self.registers.sp = self.registers.read16(Reg16::HL);
+                                self.internal_cycle();
+                            }
+                            _ => unreachable!("2-bit p index out of range"),
+                        }
+                    }
+                }
+                2 => match y {
+                    0..=3 => self.jp_if(mb, decode_condition(y)),
+                    4 => {
+                        let a = self.registers.a;
+                        self.write(mb, ZeroPage::C, a);
+                    }
+                    5 => {
+                        let a = self.registers.a;
+                        self.write(mb, Direct8, a);
+                    }
+                    6 => self.registers.a = self.read(mb, ZeroPage::C),
+                    7 => self.registers.a = self.read(mb, Direct8),
+                    _ => unreachable!("3-bit y index out of range"),
+                },
+                3 => match y {
+                    0 => self.jp(mb),
+                    1 => {
+                        let cb_opcode = self.fetch_byte(mb);
+                        self.decode_execute_cb(mb, cb_opcode);
+                    }
+                    6 => {
+                        self.ime = false;
+                        self.ei_delay = 0;
+                    }
+                    // EI's enable is delayed until after the following
+                    // instruction; see `ei_delay`.
+                    7 => self.ei_delay = 2,
+                    _ => self.illegal_opcode(0xC0 | (y << 3) | z),
+                },
+                4 => match y {
+                    0..=3 => self.call_if(mb, decode_condition(y)),
+                    _ => self.illegal_opcode(0xC0 | (y << 3) | z),
+                },
+                5 => {
+                    if q == 0 {
+                        let rp = decode_rp2_stack(p);
+                        let word = self.registers.read16(rp);
+                        self.push(mb, word);
+                    } else if p == 0 {
+                        self.call(mb);
+                    } else {
+                        self.illegal_opcode(0xC0 | (y << 3) | z);
+                    }
+                }
+                6 => self.alu(mb, y, Immediate8),
+                7 => self.rst(mb, (y as u16) * 8),
+                _ => unreachable!("3-bit z index out of range"),
+            }
         }
 
-        fn fetch_byte(&mut self, mb: &Motherboard) -> u8 {
+        /// The `0xCB`-prefixed table: `x=0` rotate/shift, `x=1` `BIT`,
+        /// `x=2` `RES`, `x=3` `SET`, all operating on `r[z]`.
+        fn decode_execute_cb(&mut self, mb: &mut Motherboard, opcode: u8) {
+            let x = opcode >> 6;
+            let y = (opcode >> 3) & 0x07;
+            let z = opcode & 0x07;
+            let operand = decode_r8(z);
+
+            match x {
+                0 => self.rot(mb, y, operand),
+                1 => self.bit(mb, y, operand),
+                2 => self.res(mb, y, operand),
+                3 => self.set(mb, y, operand),
+                _ => unreachable!("2-bit CB block index out of range"),
+            }
+        }
+
+        fn illegal_opcode(&self, opcode: u8) -> ! {
+            panic!("illegal SM83 opcode: {opcode:#04X}");
+        }
+
+        fn ld<S, D>(&mut self, mb: &mut Motherboard, destination: D, source: S)
+        where
+            S: Copy,
+            D: Copy,
+            Self: In8<S> + Out8<D>,
+        {
+            let value = self.read(mb, source);
+            self.write(mb, destination, value);
+        }
+
+        fn ld16<S, D>(&mut self, mb: &mut Motherboard, destination: D, source: S)
+        where
+            S: Copy,
+            D: Copy,
+            Self: In16<S> + Out16<D>,
+        {
+            let word = self.read16(mb, source);
+            self.write16(mb, destination, word);
+        }
+
+        fn inc8<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let result = value.wrapping_add(1);
+            self.write(mb, operand, result);
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, value & 0x0F == 0x0F);
+        }
+
+        fn dec8<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let result = value.wrapping_sub(1);
+            self.write(mb, operand, result);
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.insert(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, value & 0x0F == 0x00);
+        }
+
+        fn alu<S>(&mut self, mb: &mut Motherboard, op: u8, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            match op {
+                0 => self.add8(mb, source),
+                1 => self.adc8(mb, source),
+                2 => self.sub8(mb, source),
+                3 => self.sbc8(mb, source),
+                4 => self.and8(mb, source),
+                5 => self.xor8(mb, source),
+                6 => self.or8(mb, source),
+                7 => self.cp8(mb, source),
+                _ => unreachable!("3-bit ALU selector out of range"),
+            }
+        }
+
+        fn add8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            let (result, carry) = self.registers.a.overflowing_add(value);
+            let half_carry = (self.registers.a & 0x0F) + (value & 0x0F) > 0x0F;
+            self.registers.a = result;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, half_carry);
+            self.registers.f.set(Flags::CARRY, carry);
+        }
+
+        fn adc8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            let carry_in = self.registers.f.contains(Flags::CARRY) as u8;
+            let (partial, carry1) = self.registers.a.overflowing_add(value);
+            let (result, carry2) = partial.overflowing_add(carry_in);
+            let half_carry = (self.registers.a & 0x0F) + (value & 0x0F) + carry_in > 0x0F;
+            self.registers.a = result;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, half_carry);
+            self.registers.f.set(Flags::CARRY, carry1 || carry2);
+        }
+
+        fn sub8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            let (result, borrow) = self.registers.a.overflowing_sub(value);
+            let half_borrow = (self.registers.a & 0x0F) < (value & 0x0F);
+            self.registers.a = result;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.insert(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, half_borrow);
+            self.registers.f.set(Flags::CARRY, borrow);
+        }
+
+        fn sbc8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            let carry_in = self.registers.f.contains(Flags::CARRY) as u8;
+            let a = self.registers.a;
+            let result = a.wrapping_sub(value).wrapping_sub(carry_in);
+            let borrow = (a as u16) < (value as u16) + (carry_in as u16);
+            let half_borrow = (a & 0x0F) < (value & 0x0F) + carry_in;
+            self.registers.a = result;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.insert(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, half_borrow);
+            self.registers.f.set(Flags::CARRY, borrow);
+        }
+
+        fn and8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            self.registers.a &= value;
+            let result = self.registers.a;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.insert(Flags::HALF_CARRY);
+            self.registers.f.remove(Flags::CARRY);
+        }
+
+        fn xor8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            self.registers.a ^= value;
+            let result = self.registers.a;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers
+                .f
+                .remove(Flags::SUBTRACT | Flags::HALF_CARRY | Flags::CARRY);
+        }
+
+        fn or8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            self.registers.a |= value;
+            let result = self.registers.a;
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers
+                .f
+                .remove(Flags::SUBTRACT | Flags::HALF_CARRY | Flags::CARRY);
+        }
+
+        fn cp8<S>(&mut self, mb: &mut Motherboard, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            let (result, borrow) = self.registers.a.overflowing_sub(value);
+            let half_borrow = (self.registers.a & 0x0F) < (value & 0x0F);
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.insert(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, half_borrow);
+            self.registers.f.set(Flags::CARRY, borrow);
+        }
+
+        fn set_shift_flags(&mut self, result: u8, carry: bool) {
+            self.registers.f.set(Flags::ZERO, result == 0);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.remove(Flags::HALF_CARRY);
+            self.registers.f.set(Flags::CARRY, carry);
+        }
+
+        fn rot<D>(&mut self, mb: &mut Motherboard, op: u8, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            match op {
+                0 => self.rlc(mb, operand),
+                1 => self.rrc(mb, operand),
+                2 => self.rl(mb, operand),
+                3 => self.rr(mb, operand),
+                4 => self.sla(mb, operand),
+                5 => self.sra(mb, operand),
+                6 => self.swap(mb, operand),
+                7 => self.srl(mb, operand),
+                _ => unreachable!("3-bit rotate selector out of range"),
+            }
+        }
+
+        fn rlc<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry = value & 0x80 != 0;
+            let result = value.rotate_left(1);
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry);
+        }
+
+        fn rrc<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry = value & 0x01 != 0;
+            let result = value.rotate_right(1);
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry);
+        }
+
+        fn rl<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry_in = self.registers.f.contains(Flags::CARRY) as u8;
+            let carry_out = value & 0x80 != 0;
+            let result = (value << 1) | carry_in;
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry_out);
+        }
+
+        fn rr<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry_in = self.registers.f.contains(Flags::CARRY) as u8;
+            let carry_out = value & 0x01 != 0;
+            let result = (value >> 1) | (carry_in << 7);
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry_out);
+        }
+
+        fn sla<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry = value & 0x80 != 0;
+            let result = value << 1;
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry);
+        }
+
+        fn sra<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry = value & 0x01 != 0;
+            let result = (value >> 1) | (value & 0x80);
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry);
+        }
+
+        fn swap<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let result = value.rotate_right(4);
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, false);
+        }
+
+        fn srl<D>(&mut self, mb: &mut Motherboard, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            let carry = value & 0x01 != 0;
+            let result = value >> 1;
+            self.write(mb, operand, result);
+            self.set_shift_flags(result, carry);
+        }
+
+        fn bit<S>(&mut self, mb: &mut Motherboard, index: u8, source: S)
+        where
+            S: Copy,
+            Self: In8<S>,
+        {
+            let value = self.read(mb, source);
+            let is_set = value & (1 << index) != 0;
+            self.registers.f.set(Flags::ZERO, !is_set);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.insert(Flags::HALF_CARRY);
+        }
+
+        fn res<D>(&mut self, mb: &mut Motherboard, index: u8, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            self.write(mb, operand, value & !(1 << index));
+        }
+
+        fn set<D>(&mut self, mb: &mut Motherboard, index: u8, operand: D)
+        where
+            D: Copy,
+            Self: In8<D> + Out8<D>,
+        {
+            let value = self.read(mb, operand);
+            self.write(mb, operand, value | (1 << index));
+        }
+
+        fn rlca(&mut self) {
+            let value = self.registers.a;
+            let carry = value & 0x80 != 0;
+            self.registers.a = value.rotate_left(1);
+            self.registers.f = Flags::empty();
+            self.registers.f.set(Flags::CARRY, carry);
+        }
+
+        fn rrca(&mut self) {
+            let value = self.registers.a;
+            let carry = value & 0x01 != 0;
+            self.registers.a = value.rotate_right(1);
+            self.registers.f = Flags::empty();
+            self.registers.f.set(Flags::CARRY, carry);
+        }
+
+        fn rla(&mut self) {
+            let value = self.registers.a;
+            let carry_in = self.registers.f.contains(Flags::CARRY) as u8;
+            let carry_out = value & 0x80 != 0;
+            self.registers.a = (value << 1) | carry_in;
+            self.registers.f = Flags::empty();
+            self.registers.f.set(Flags::CARRY, carry_out);
+        }
+
+        fn rra(&mut self) {
+            let value = self.registers.a;
+            let carry_in = self.registers.f.contains(Flags::CARRY) as u8;
+            let carry_out = value & 0x01 != 0;
+            self.registers.a = (value >> 1) | (carry_in << 7);
+            self.registers.f = Flags::empty();
+            self.registers.f.set(Flags::CARRY, carry_out);
+        }
+
+        fn daa(&mut self) {
+            let mut a = self.registers.a;
+            let mut carry = self.registers.f.contains(Flags::CARRY);
+            if !self.registers.f.contains(Flags::SUBTRACT) {
+                if carry || a > 0x99 {
+                    a = a.wrapping_add(0x60);
+                    carry = true;
+                }
+                if self.registers.f.contains(Flags::HALF_CARRY) || (a & 0x0F) > 0x09 {
+                    a = a.wrapping_add(0x06);
+                }
+            } else {
+                if carry {
+                    a = a.wrapping_sub(0x60);
+                }
+                if self.registers.f.contains(Flags::HALF_CARRY) {
+                    a = a.wrapping_sub(0x06);
+                }
+            }
+            self.registers.a = a;
+            self.registers.f.set(Flags::ZERO, a == 0);
+            self.registers.f.remove(Flags::HALF_CARRY);
+            self.registers.f.set(Flags::CARRY, carry);
+        }
+
+        fn cpl(&mut self) {
+            self.registers.a = !self.registers.a;
+            self.registers.f.insert(Flags::SUBTRACT | Flags::HALF_CARRY);
+        }
+
+        fn scf(&mut self) {
+            self.registers.f.remove(Flags::SUBTRACT | Flags::HALF_CARRY);
+            self.registers.f.insert(Flags::CARRY);
+        }
+
+        fn ccf(&mut self) {
+            self.registers.f.remove(Flags::SUBTRACT | Flags::HALF_CARRY);
+            self.registers.f.toggle(Flags::CARRY);
+        }
+
+        fn inc16(&mut self, rp: Reg16) {
+            let value = self.registers.read16(rp).wrapping_add(1);
+            self.registers.write16(rp, value);
+            self.internal_cycle();
+        }
+
+        fn dec16(&mut self, rp: Reg16) {
+            let value = self.registers.read16(rp).wrapping_sub(1);
+            self.registers.write16(rp, value);
+            self.internal_cycle();
+        }
+
+        fn add_hl(&mut self, rp: Reg16) {
+            let hl = self.registers.read16(Reg16::HL);
+            let value = self.registers.read16(rp);
+            let (result, carry) = hl.overflowing_add(value);
+            let half_carry = (hl & 0x0FFF) + (value & 0x0FFF) > 0x0FFF;
+            self.registers.write16(Reg16::HL, result);
+            self.registers.f.remove(Flags::SUBTRACT);
+            self.registers.f.set(Flags::HALF_CARRY, half_carry);
+            self.registers.f.set(Flags::CARRY, carry);
+            self.internal_cycle();
+        }
+
+        fn add_sp_r8(&mut self, mb: &mut Motherboard) {
+            let offset = self.fetch_byte(mb) as i8 as i16 as u16;
+            let sp = self.registers.sp;
+            let half_carry = (sp & 0x0F) + (offset & 0x0F) > 0x0F;
+            let carry = (sp & 0xFF) + (offset & 0xFF) > 0xFF;
+            self.registers.sp = sp.wrapping_add(offset);
+            self.registers.f = Flags::empty();
+            self.registers.f.set(Flags::HALF_CARRY, half_carry);
+            self.registers.f.set(Flags::CARRY, carry);
+            // ADD SP,r8 spends two internal M-cycles past the offset read:
+            // one computing the 16-bit sum, one writing it back to SP.
+            self.internal_cycle();
+            self.internal_cycle();
+        }
+
+        fn ld_hl_sp_plus_r8(&mut self, mb: &mut Motherboard) {
+            let offset = self.fetch_byte(mb) as i8 as i16 as u16;
+            let sp = self.registers.sp;
+            let half_carry = (sp & 0x0F) + (offset & 0x0F) > 0x0F;
+            let carry = (sp & 0xFF) + (offset & 0xFF) > 0xFF;
+            self.registers.write16(Reg16::HL, sp.wrapping_add(offset));
+            self.registers.f = Flags::empty();
+            self.registers.f.set(Flags::HALF_CARRY, half_carry);
+            self.registers.f.set(Flags::CARRY, carry);
+            self.internal_cycle();
+        }
+
+        /// Adjusts HL by `delta` with no cycle charge of its own, for
+        /// `LD (HL+),A`/`LD (HL-),A`/`LD A,(HL+)`/`LD A,(HL-)`, where the
+        /// increment/decrement rides along on the load's own bus access
+        /// instead of costing a separate internal M-cycle like standalone
+        /// `INC rr`/`DEC rr` do.
+        fn bump_hl(&mut self, delta: i16) {
+            let hl = self.registers.read16(Reg16::HL);
+            self.registers.write16(Reg16::HL, hl.wrapping_add(delta as u16));
+        }
+
+        fn ld_indirect_a(&mut self, mb: &mut Motherboard, p: u8) {
+            let a = self.registers.a;
+            match p {
+                0 => self.write(mb, Indirect(Reg16::BC), a),
+                1 => self.write(mb, Indirect(Reg16::DE), a),
+                2 => {
+                    self.write(mb, Indirect(Reg16::HL), a);
+                    self.bump_hl(1);
+                }
+                3 => {
+                    self.write(mb, Indirect(Reg16::HL), a);
+                    self.bump_hl(-1);
+                }
+                _ => unreachable!("2-bit p index out of range"),
+            }
+        }
+
+        fn ld_a_indirect(&mut self, mb: &mut Motherboard, p: u8) {
+            self.registers.a = match p {
+                0 => self.read(mb, Indirect(Reg16::BC)),
+                1 => self.read(mb, Indirect(Reg16::DE)),
+                2 => {
+                    let value = self.read(mb, Indirect(Reg16::HL));
+                    self.bump_hl(1);
+                    value
+                }
+                3 => {
+                    let value = self.read(mb, Indirect(Reg16::HL));
+                    self.bump_hl(-1);
+                    value
+                }
+                _ => unreachable!("2-bit p index out of range"),
+            };
+        }
+
+        fn test_condition(&self, condition: Condition) -> bool {
+            match condition {
+                Condition::NotZero => !self.registers.f.contains(Flags::ZERO),
+                Condition::Zero => self.registers.f.contains(Flags::ZERO),
+                Condition::NotCarry => !self.registers.f.contains(Flags::CARRY),
+                Condition::Carry => self.registers.f.contains(Flags::CARRY),
+            }
+        }
+
+        fn jr(&mut self, mb: &mut Motherboard) {
+            let offset = self.fetch_byte(mb) as i8 as i16;
+            self.registers.pc = self.registers.pc.wrapping_add(offset as u16);
+            self.internal_cycle();
+        }
+
+        fn jr_if(&mut self, mb: &mut Motherboard, condition: Condition) {
+            let offset = self.fetch_byte(mb) as i8 as i16;
+            if self.test_condition(condition) {
+                self.registers.pc = self.registers.pc.wrapping_add(offset as u16);
+                self.internal_cycle();
+            }
+        }
+
+        fn jp(&mut self, mb: &mut Motherboard) {
+            self.registers.pc = self.fetch_word(mb);
+            self.internal_cycle();
+        }
+
+        fn jp_if(&mut self, mb: &mut Motherboard, condition: Condition) {
+            let address = self.fetch_word(mb);
+            if self.test_condition(condition) {
+                self.registers.pc = address;
+                self.internal_cycle();
+            }
+        }
+
+        fn call(&mut self, mb: &mut Motherboard) {
+            let address = self.fetch_word(mb);
+            let return_address = self.registers.pc;
+            self.push(mb, return_address);
+            self.registers.pc = address;
+        }
+
+        fn call_if(&mut self, mb: &mut Motherboard, condition: Condition) {
+            let address = self.fetch_word(mb);
+            if self.test_condition(condition) {
+                let return_address = self.registers.pc;
+                self.push(mb, return_address);
+                self.registers.pc = address;
+            }
+        }
+
+        fn ret(&mut self, mb: &mut Motherboard) {
+            self.registers.pc = self.pop(mb);
+            self.internal_cycle();
+        }
+
+        fn ret_if(&mut self, mb: &mut Motherboard, condition: Condition) {
+            // RET cc always spends an internal M-cycle testing the
+            // condition, whether or not it's taken.
+            self.internal_cycle();
+            if self.test_condition(condition) {
+                self.registers.pc = self.pop(mb);
+                self.internal_cycle();
+            }
+        }
+
+        fn rst(&mut self, mb: &mut Motherboard, vector: u16) {
+            let return_address = self.registers.pc;
+            self.push(mb, return_address);
+            self.registers.pc = vector;
+        }
+
+        fn push(&mut self, mb: &mut Motherboard, word: u16) {
+            // PUSH spends an internal M-cycle decrementing `sp` before the
+            // first write goes out on the bus.
+            self.internal_cycle();
+            self.registers.sp = self.registers.sp.wrapping_sub(1);
+            self.write_byte(mb, self.registers.sp, (word >> 8) as u8);
+            self.registers.sp = self.registers.sp.wrapping_sub(1);
+            self.write_byte(mb, self.registers.sp, word as u8);
+        }
+
+        fn pop(&mut self, mb: &mut Motherboard) -> u16 {
+            let low = self.read_byte(mb, self.registers.sp);
+            self.registers.sp = self.registers.sp.wrapping_add(1);
+            let high = self.read_byte(mb, self.registers.sp);
+            self.registers.sp = self.registers.sp.wrapping_add(1);
+            u16::from_le_bytes([low, high])
+        }
+
+        fn fetch_byte(&mut self, mb: &mut Motherboard) -> u8 {
             let address = self.registers.pc;
-            let byte = mb.memory_map.read_byte_at(address);
+            let byte = self.read_byte(mb, address);
             self.registers.pc = address.wrapping_add(1);
             byte
         }
 
-        fn fetch_word(&mut self, mb: &Motherboard) -> u16 {
+        fn fetch_word(&mut self, mb: &mut Motherboard) -> u16 {
             let low = self.fetch_byte(mb);
             let high = self.fetch_byte(mb);
             u16::from_le_bytes([low, high])
         }
     }
 
+    fn reg8_name(reg: Reg8) -> &'static str {
+        match reg {
+            Reg8::A => "A",
+            Reg8::F => "F",
+            Reg8::B => "B",
+            Reg8::C => "C",
+            Reg8::D => "D",
+            Reg8::E => "E",
+            Reg8::H => "H",
+            Reg8::L => "L",
+        }
+    }
+
+    fn r8_name(r8: R8) -> &'static str {
+        match r8 {
+            R8::Reg(reg) => reg8_name(reg),
+            R8::IndirectHl => "(HL)",
+        }
+    }
+
+    fn reg16_name(reg: Reg16) -> &'static str {
+        match reg {
+            Reg16::AF => "AF",
+            Reg16::BC => "BC",
+            Reg16::DE => "DE",
+            Reg16::HL => "HL",
+            Reg16::SP => "SP",
+        }
+    }
+
+    fn condition_name(condition: Condition) -> &'static str {
+        match condition {
+            Condition::NotZero => "NZ",
+            Condition::Zero => "Z",
+            Condition::NotCarry => "NC",
+            Condition::Carry => "C",
+        }
+    }
+
+    /// `(rp)` for `LD (rp),A`/`LD A,(rp)`; see [`SM83::ld_indirect_a`]/
+    /// [`SM83::ld_a_indirect`].
+    fn indirect_a_operand_name(p: u8) -> &'static str {
+        match p {
+            0 => "(BC)",
+            1 => "(DE)",
+            2 => "(HL+)",
+            3 => "(HL-)",
+            _ => unreachable!("2-bit p index out of range"),
+        }
+    }
+
+    const ALU_MNEMONICS: [&str; 8] = ["ADD A,", "ADC A,", "SUB ", "SBC A,", "AND ", "XOR ", "OR ", "CP "];
+    const ROT_MNEMONICS: [&str; 8] = ["RLC ", "RRC ", "RL ", "RR ", "SLA ", "SRA ", "SWAP ", "SRL "];
+
+    fn peek_u8(mb: &Motherboard, address: Address) -> u8 {
+        mb.memory_map.peek_byte(address)
+    }
+
+    fn peek_u16(mb: &Motherboard, address: Address) -> u16 {
+        let low = peek_u8(mb, address);
+        let high = peek_u8(mb, address.wrapping_add(1));
+        u16::from_le_bytes([low, high])
+    }
+
+    /// Decodes one instruction at `address` into a mnemonic and its length
+    /// in bytes, mirroring [`SM83::decode_execute`]'s block dispatch but
+    /// without touching CPU state or bus timing, so a debugger can
+    /// disassemble ahead of `pc` or across a whole ROM image.
+    pub(crate) fn disassemble(mb: &Motherboard, address: Address) -> (String, u16) {
+        let opcode = peek_u8(mb, address);
+        if opcode == 0xCB {
+            let cb_opcode = peek_u8(mb, address.wrapping_add(1));
+            return (disassemble_cb(cb_opcode), 2);
+        }
+
+        let x = opcode >> 6;
+        let y = (opcode >> 3) & 0x07;
+        let z = opcode & 0x07;
+        let p = y >> 1;
+        let q = y & 0x01;
+
+        match x {
+            0 => disassemble_block0(mb, address, y, z, p, q),
+            1 => {
+                if y == 6 && z == 6 {
+                    ("HALT".to_string(), 1)
+                } else {
+                    (format!("LD {},{}", r8_name(decode_r8(y)), r8_name(decode_r8(z))), 1)
+                }
+            }
+            2 => (format!("{}{}", ALU_MNEMONICS[y as usize], r8_name(decode_r8(z))), 1),
+            3 => disassemble_block3(mb, address, y, z, p, q),
+            _ => unreachable!("2-bit block index out of range"),
+        }
+    }
+
+    fn disassemble_block0(mb: &Motherboard, address: Address, y: u8, z: u8, p: u8, q: u8) -> (String, u16) {
+        match z {
+            0 => match y {
+                0 => ("NOP".to_string(), 1),
+                1 => {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("LD ({target:#06X}),SP"), 3)
+                }
+                2 => ("STOP".to_string(), 2),
+                3 => {
+                    let offset = peek_u8(mb, address.wrapping_add(1)) as i8;
+                    (format!("JR {offset}"), 2)
+                }
+                _ => {
+                    let offset = peek_u8(mb, address.wrapping_add(1)) as i8;
+                    (format!("JR {},{offset}", condition_name(decode_condition(y - 4))), 2)
+                }
+            },
+            1 => {
+                let rp = reg16_name(decode_rp(p));
+                if q == 0 {
+                    let immediate = peek_u16(mb, address.wrapping_add(1));
+                    (format!("LD {rp},{immediate:#06X}"), 3)
+                } else {
+                    (format!("ADD HL,{rp}"), 1)
+                }
+            }
+            2 => {
+                let operand = indirect_a_operand_name(p);
+                if q == 0 {
+                    (format!("LD {operand},A"), 1)
+                } else {
+                    (format!("LD A,{operand}"), 1)
+                }
+            }
+            3 => {
+                let rp = reg16_name(decode_rp(p));
+                if q == 0 {
+                    (format!("INC {rp}"), 1)
+                } else {
+                    (format!("DEC {rp}"), 1)
+                }
+            }
+            4 => (format!("INC {}", r8_name(decode_r8(y))), 1),
+            5 => (format!("DEC {}", r8_name(decode_r8(y))), 1),
+            6 => {
+                let immediate = peek_u8(mb, address.wrapping_add(1));
+                (format!("LD {},{immediate:#04X}", r8_name(decode_r8(y))), 2)
+            }
+            7 => (
+                ["RLCA", "RRCA", "RLA", "RRA", "DAA", "CPL", "SCF", "CCF"][y as usize].to_string(),
+                1,
+            ),
+            _ => unreachable!("3-bit z index out of range"),
+        }
+    }
+
+    fn disassemble_block3(mb: &Motherboard, address: Address, y: u8, z: u8, p: u8, q: u8) -> (String, u16) {
+        match z {
+            0 => match y {
+                0..=3 => (format!("RET {}", condition_name(decode_condition(y))), 1),
+                4 => {
+                    let offset = peek_u8(mb, address.wrapping_add(1));
+                    (format!("LDH ({:#04X}),A", 0xFF00 + offset as u16), 2)
+                }
+                5 => {
+                    let offset = peek_u8(mb, address.wrapping_add(1)) as i8;
+                    (format!("ADD SP,{offset}"), 2)
+                }
+                6 => {
+                    let offset = peek_u8(mb, address.wrapping_add(1));
+                    (format!("LDH A,({:#04X})", 0xFF00 + offset as u16), 2)
+                }
+                7 => {
+                    let offset = peek_u8(mb, address.wrapping_add(1)) as i8;
+                    (format!("LD HL,SP{offset:+}"), 2)
+                }
+                _ => unreachable!("3-bit y index out of range"),
+            },
+            1 => {
+                if q == 0 {
+                    (format!("POP {}", reg16_name(decode_rp2_stack(p))), 1)
+                } else {
+                    match p {
+                        0 => ("RET".to_string(), 1),
+                        1 => ("RETI".to_string(), 1),
+                        2 => ("JP (HL)".to_string(), 1),
+                        3 => ("LD SP,HL".to_string(), 1),
+                        _ => unreachable!("2-bit p index out of range"),
+                    }
+                }
+            }
+            2 => match y {
+                0..=3 => {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("JP {},{target:#06X}", condition_name(decode_condition(y))), 3)
+                }
+                4 => ("LD (C),A".to_string(), 1),
+                5 => {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("LD ({target:#06X}),A"), 3)
+                }
+                6 => ("LD A,(C)".to_string(), 1),
+                7 => {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("LD A,({target:#06X})"), 3)
+                }
+                _ => unreachable!("3-bit y index out of range"),
+            },
+            3 => match y {
+                0 => {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("JP {target:#06X}"), 3)
+                }
+                1 => {
+                    let cb_opcode = peek_u8(mb, address.wrapping_add(1));
+                    (disassemble_cb(cb_opcode), 2)
+                }
+                6 => ("DI".to_string(), 1),
+                7 => ("EI".to_string(), 1),
+                _ => (format!("DB {:#04X}", 0xC0 | (y << 3) | z), 1),
+            },
+            4 => match y {
+                0..=3 => {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("CALL {},{target:#06X}", condition_name(decode_condition(y))), 3)
+                }
+                _ => (format!("DB {:#04X}", 0xC0 | (y << 3) | z), 1),
+            },
+            5 => {
+                if q == 0 {
+                    (format!("PUSH {}", reg16_name(decode_rp2_stack(p))), 1)
+                } else if p == 0 {
+                    let target = peek_u16(mb, address.wrapping_add(1));
+                    (format!("CALL {target:#06X}"), 3)
+                } else {
+                    (format!("DB {:#04X}", 0xC0 | (y << 3) | z), 1)
+                }
+            }
+            6 => {
+                let immediate = peek_u8(mb, address.wrapping_add(1));
+                (format!("{}{immediate:#04X}", ALU_MNEMONICS[y as usize]), 2)
+            }
+            7 => (format!("RST {:#04X}", (y as u16) * 8), 1),
+            _ => unreachable!("3-bit z index out of range"),
+        }
+    }
+
+    /// The `0xCB`-prefixed table; mirrors [`SM83::decode_execute_cb`].
+    fn disassemble_cb(opcode: u8) -> String {
+        let x = opcode >> 6;
+        let y = (opcode >> 3) & 0x07;
+        let z = opcode & 0x07;
+        let operand = r8_name(decode_r8(z));
+
+        match x {
+            0 => format!("{}{operand}", ROT_MNEMONICS[y as usize]),
+            1 => format!("BIT {y},{operand}"),
+            2 => format!("RES {y},{operand}"),
+            3 => format!("SET {y},{operand}"),
+            _ => unreachable!("2-bit CB block index out of range"),
+        }
+    }
+
+    /// Why a [`Debugger::run_until`] call stopped.
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub(crate) enum StopReason {
+        ReachedPc,
+        HitPcBreakpoint,
+        HitOpcodeBreakpoint,
+        InstructionLimit,
+    }
+
+    /// A minimal step-debugger layered over [`SM83`]/[`Motherboard`]: single
+    /// stepping, running to a target `pc` or breakpoint, and inspecting or
+    /// editing registers and memory. Like the rest of the CPU's free
+    /// functions, it takes the `SM83`/`Motherboard` it operates on as
+    /// arguments rather than owning them, so it can sit alongside whichever
+    /// motherboard a caller (e.g. a test harness) is already driving.
+    #[derive(Default)]
+    pub(crate) struct Debugger {
+        pc_breakpoints: std::collections::HashSet<Address>,
+        opcode_breakpoints: std::collections::HashSet<u8>,
+    }
+
+    impl Debugger {
+        pub(crate) fn break_at_pc(&mut self, address: Address) {
+            self.pc_breakpoints.insert(address);
+        }
+
+        pub(crate) fn break_on_opcode(&mut self, opcode: u8) {
+            self.opcode_breakpoints.insert(opcode);
+        }
+
+        /// Disassembles the instruction `cpu` is about to fetch.
+        pub(crate) fn disassemble_next(&self, cpu: &SM83, mb: &Motherboard) -> (String, u16) {
+            disassemble(mb, cpu.registers.pc)
+        }
+
+        /// Executes a single instruction, returning the T-cycles it took.
+        pub(crate) fn step(&self, cpu: &mut SM83, mb: &mut Motherboard) -> u8 {
+            cpu.step(mb)
+        }
+
+        /// Steps `cpu` until its `pc` reaches `target`, a breakpoint is hit,
+        /// or `max_instructions` have executed (so a ROM that never reaches
+        /// `target` can't hang the caller).
+        pub(crate) fn run_until(
+            &self,
+            cpu: &mut SM83,
+            mb: &mut Motherboard,
+            target: Address,
+            max_instructions: u32,
+        ) -> StopReason {
+            for _ in 0..max_instructions {
+                if cpu.registers.pc == target {
+                    return StopReason::ReachedPc;
+                }
+                if self.pc_breakpoints.contains(&cpu.registers.pc) {
+                    return StopReason::HitPcBreakpoint;
+                }
+                if self.opcode_breakpoints.contains(&peek_u8(mb, cpu.registers.pc)) {
+                    return StopReason::HitOpcodeBreakpoint;
+                }
+                cpu.step(mb);
+            }
+            StopReason::InstructionLimit
+        }
+
+        /// A one-line trace of the register file, flags and `pc`.
+        pub(crate) fn dump_registers(&self, cpu: &SM83) -> String {
+            let f = cpu.registers.f;
+            format!(
+                "A={:02X} F={:02X} [{}{}{}{}] BC={:04X} DE={:04X} HL={:04X} SP={:04X} PC={:04X}",
+                cpu.registers.a,
+                f.bits,
+                if f.contains(Flags::ZERO) { 'Z' } else { '-' },
+                if f.contains(Flags::SUBTRACT) { 'N' } else { '-' },
+                if f.contains(Flags::HALF_CARRY) { 'H' } else { '-' },
+                if f.contains(Flags::CARRY) { 'C' } else { '-' },
+                cpu.registers.read16(Reg16::BC),
+                cpu.registers.read16(Reg16::DE),
+                cpu.registers.read16(Reg16::HL),
+                cpu.registers.sp,
+                cpu.registers.pc,
+            )
+        }
+
+        pub(crate) fn read_register8(&self, cpu: &SM83, reg: Reg8) -> u8 {
+            match reg {
+                Reg8::A => cpu.registers.a,
+                Reg8::F => cpu.registers.f.bits,
+                Reg8::B => cpu.registers.b,
+                Reg8::C => cpu.registers.c,
+                Reg8::D => cpu.registers.d,
+                Reg8::E => cpu.registers.e,
+                Reg8::H => cpu.registers.h,
+                Reg8::L => cpu.registers.l,
+            }
+        }
+
+        pub(crate) fn write_register8(&self, cpu: &mut SM83, reg: Reg8, value: u8) {
+            match reg {
+                Reg8::A => cpu.registers.a = value,
+                Reg8::F => cpu.registers.f = Flags::from_bits_truncate(value),
+                Reg8::B => cpu.registers.b = value,
+                Reg8::C => cpu.registers.c = value,
+                Reg8::D => cpu.registers.d = value,
+                Reg8::E => cpu.registers.e = value,
+                Reg8::H => cpu.registers.h = value,
+                Reg8::L => cpu.registers.l = value,
+            }
+        }
+
+        pub(crate) fn read_register16(&self, cpu: &SM83, reg: Reg16) -> u16 {
+            cpu.registers.read16(reg)
+        }
+
+        pub(crate) fn write_register16(&self, cpu: &mut SM83, reg: Reg16, value: u16) {
+            cpu.registers.write16(reg, value);
+        }
+
+        pub(crate) fn read_memory(&self, mb: &Motherboard, address: Address) -> u8 {
+            peek_u8(mb, address)
+        }
+
+        pub(crate) fn write_memory(&self, mb: &mut Motherboard, address: Address, data: u8) {
+            mb.memory_map.poke_byte(address, data);
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -201,5 +1798,391 @@ mod Core {
         fn we_can_instantiate_cpu() {
             let cpu = SM83::default();
         }
+
+        #[test]
+        fn nop_advances_pc_by_one() {
+            let ram = crate::bus::FlatRam::default(); // byte 0 is 0x00: NOP
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            cpu.fetch_decode_execute(&mut mb);
+            assert_eq!(cpu.registers.pc, 1);
+        }
+
+        #[test]
+        fn ld_b_a_copies_register() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83::default();
+            cpu.registers.a = 0x42;
+            cpu.ld(&mut mb, Reg8::B, Reg8::A);
+            assert_eq!(cpu.registers.b, 0x42);
+        }
+
+        #[test]
+        fn add_sets_carry_and_half_carry_but_not_zero() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83::default();
+            cpu.registers.a = 0xFF;
+            cpu.add8(&mut mb, Reg8::A);
+            assert_eq!(cpu.registers.a, 0xFE);
+            assert!(cpu.registers.f.contains(Flags::CARRY));
+            assert!(cpu.registers.f.contains(Flags::HALF_CARRY));
+            assert!(!cpu.registers.f.contains(Flags::ZERO));
+        }
+
+        #[test]
+        fn write_to_f_masks_low_nibble() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83::default();
+            cpu.write(&mut mb, Reg8::F, 0xFF);
+            assert_eq!(cpu.registers.f.bits, 0xF0);
+        }
+
+        #[test]
+        fn bit_instruction_sets_zero_when_clear() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83::default();
+            cpu.registers.b = 0x00;
+            cpu.bit(&mut mb, 0, Reg8::B);
+            assert!(cpu.registers.f.contains(Flags::ZERO));
+            assert!(cpu.registers.f.contains(Flags::HALF_CARRY));
+        }
+
+        #[test]
+        fn step_reports_one_m_cycle_for_nop() {
+            let ram = crate::bus::FlatRam::default(); // byte 0 is 0x00: NOP
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            let cycles = cpu.step(&mut mb);
+            assert_eq!(cycles, CYCLES_PER_M_CYCLE);
+            assert_eq!(cpu.cycles(), CYCLES_PER_M_CYCLE as u64);
+        }
+
+        #[test]
+        fn indirect_read_costs_one_m_cycle() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83::default();
+            let before = cpu.cycles();
+            cpu.read(&mut mb, Indirect(Reg16::HL));
+            assert_eq!(cpu.cycles() - before, CYCLES_PER_M_CYCLE as u64);
+        }
+
+        #[test]
+        fn ld_hl_plus_a_costs_two_m_cycles_not_three() {
+            // The HL increment rides along on this instruction's own bus
+            // access, unlike standalone INC rr/DEC rr, which spend a
+            // separate internal M-cycle.
+            let mut ram = crate::bus::FlatRam::default();
+            ram.0[0] = 0x22; // LD (HL+),A
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            cpu.registers.write16(Reg16::HL, 0xC000);
+
+            let cycles = cpu.step(&mut mb);
+
+            assert_eq!(cycles, 2 * CYCLES_PER_M_CYCLE);
+            assert_eq!(cpu.registers.read16(Reg16::HL), 0xC001);
+        }
+
+        #[test]
+        fn halted_cpu_still_idles_for_one_m_cycle() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83 {
+                state: State::Halted,
+                ..Default::default()
+            };
+            let cycles = cpu.step(&mut mb);
+            assert_eq!(cycles, CYCLES_PER_M_CYCLE);
+            assert_eq!(cpu.registers.pc, 0);
+        }
+
+        #[test]
+        fn memory_interface_is_object_safe_over_flat_ram() {
+            let mut ram = crate::bus::FlatRam::default();
+            ram.0[0] = 0x3E; // LD A,n
+            ram.0[1] = 0x99;
+            let bus: &mut dyn MemoryInterface = &mut ram;
+            let (opcode, cycles) = bus.read_byte(0);
+            assert_eq!(opcode, 0x3E);
+            assert_eq!(cycles, CYCLES_PER_M_CYCLE);
+        }
+
+        #[test]
+        fn pending_enabled_interrupt_is_serviced_when_ime_set() {
+            let ram = crate::bus::FlatRam::default();
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            mb.memory_map.write_byte(0xFFFF, 0x01); // IE: V-blank enabled
+            mb.memory_map.write_byte(0xFF0F, 0x01); // IF: V-blank pending
+            let mut cpu = SM83 {
+                ime: true,
+                ..Default::default()
+            };
+            cpu.registers.sp = 0xFFFE;
+            cpu.registers.pc = 0x0150;
+
+            cpu.step(&mut mb);
+
+            assert_eq!(cpu.registers.pc, 0x0040);
+            assert!(!cpu.ime);
+            assert_eq!(mb.memory_map.interrupt_flag() & 0x01, 0);
+            assert_eq!(cpu.pop(&mut mb), 0x0150);
+        }
+
+        #[test]
+        fn ei_enables_ime_only_after_the_following_instruction() {
+            let mut ram = crate::bus::FlatRam::default();
+            ram.0[0] = 0xFB; // EI
+            ram.0[1] = 0x00; // NOP
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+
+            cpu.step(&mut mb); // EI itself: enable is still pending
+            assert!(!cpu.ime);
+
+            cpu.step(&mut mb); // the instruction after EI: now it takes effect
+            assert!(cpu.ime);
+        }
+
+        #[test]
+        fn halt_is_woken_by_a_pending_interrupt_even_with_ime_clear() {
+            let mut mb = Motherboard::default();
+            mb.memory_map.write_byte(0xFFFF, 0x01);
+            mb.memory_map.write_byte(0xFF0F, 0x01);
+            let mut cpu = SM83 {
+                state: State::Halted,
+                ime: false,
+                ..Default::default()
+            };
+
+            cpu.step(&mut mb);
+
+            assert_eq!(cpu.state, State::Running);
+        }
+
+        #[test]
+        fn halt_bug_replays_the_following_byte_once() {
+            let mut ram = crate::bus::FlatRam::default();
+            ram.0[0] = 0x76; // HALT
+            ram.0[1] = 0x3C; // INC A
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            mb.memory_map.write_byte(0xFFFF, 0x01);
+            mb.memory_map.write_byte(0xFF0F, 0x01);
+            let mut cpu = SM83 {
+                ime: false,
+                ..Default::default()
+            };
+
+            cpu.step(&mut mb); // HALT triggers the bug rather than halting
+            assert_eq!(cpu.state, State::Running);
+            assert_eq!(cpu.registers.pc, 1);
+
+            cpu.step(&mut mb); // INC A, but pc doesn't advance past it yet
+            assert_eq!(cpu.registers.a, 1);
+            assert_eq!(cpu.registers.pc, 1);
+
+            cpu.step(&mut mb); // INC A replays for real
+            assert_eq!(cpu.registers.a, 2);
+            assert_eq!(cpu.registers.pc, 2);
+        }
+
+        #[test]
+        fn tima_overflow_reloads_from_tma_and_requests_timer_interrupt() {
+            let mut mb = Motherboard::default();
+            mb.memory_map.write_byte(0xFF07, 0x05); // TAC: enabled, period 16
+            mb.memory_map.write_byte(0xFF06, 0x7F); // TMA
+            mb.memory_map.write_byte(0xFF05, 0xFF); // TIMA: one tick from overflow
+
+            mb.memory_map.tick(16);
+
+            assert_eq!(mb.memory_map.read_byte(0xFF05).0, 0x7F);
+            assert_eq!(mb.memory_map.interrupt_flag() & 0x04, 0x04);
+        }
+
+        #[test]
+        fn writing_div_resets_the_internal_divider() {
+            let mut mb = Motherboard::default();
+            for _ in 0..300 {
+                mb.memory_map.tick(1);
+            }
+            assert_ne!(mb.memory_map.read_byte(0xFF04).0, 0);
+
+            mb.memory_map.write_byte(0xFF04, 0x42);
+
+            assert_eq!(mb.memory_map.read_byte(0xFF04).0, 0);
+        }
+
+        #[test]
+        fn disassemble_decodes_an_immediate_load() {
+            let ram = crate::bus::FlatRam::default();
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            mb.memory_map.write_byte(0x0000, 0x3E); // LD A,n
+            mb.memory_map.write_byte(0x0001, 0x7B);
+
+            let (mnemonic, length) = disassemble(&mb, 0x0000);
+
+            assert_eq!(mnemonic, "LD A,0x7B");
+            assert_eq!(length, 2);
+        }
+
+        #[test]
+        fn disassemble_decodes_a_cb_prefixed_instruction() {
+            let ram = crate::bus::FlatRam::default();
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            mb.memory_map.write_byte(0x0000, 0xCB);
+            mb.memory_map.write_byte(0x0001, 0x47); // BIT 0,A
+
+            let (mnemonic, length) = disassemble(&mb, 0x0000);
+
+            assert_eq!(mnemonic, "BIT 0,A");
+            assert_eq!(length, 2);
+        }
+
+        #[test]
+        fn disassemble_does_not_advance_the_timer() {
+            let mut mb = Motherboard::default();
+            mb.memory_map.write_byte(0x0000, 0x00); // NOP
+
+            for _ in 0..10 {
+                disassemble(&mb, 0x0000);
+            }
+
+            assert_eq!(mb.memory_map.read_byte(0xFF04).0, 0);
+        }
+
+        #[test]
+        fn debugger_steps_a_single_instruction() {
+            let ram = crate::bus::FlatRam::default(); // byte 0 is 0x00: NOP
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            let debugger = Debugger::default();
+
+            debugger.step(&mut cpu, &mut mb);
+
+            assert_eq!(cpu.registers.pc, 1);
+        }
+
+        #[test]
+        fn debugger_runs_until_target_pc() {
+            let ram = crate::bus::FlatRam::default(); // all zero: NOPs
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            let debugger = Debugger::default();
+
+            let reason = debugger.run_until(&mut cpu, &mut mb, 0x0002, 10);
+
+            assert_eq!(reason, StopReason::ReachedPc);
+            assert_eq!(cpu.registers.pc, 0x0002);
+        }
+
+        #[test]
+        fn debugger_stops_at_a_pc_breakpoint() {
+            let ram = crate::bus::FlatRam::default(); // all zero: NOPs
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            let mut debugger = Debugger::default();
+            debugger.break_at_pc(0x0001);
+
+            let reason = debugger.run_until(&mut cpu, &mut mb, 0x0002, 10);
+
+            assert_eq!(reason, StopReason::HitPcBreakpoint);
+            assert_eq!(cpu.registers.pc, 0x0001);
+        }
+
+        #[test]
+        fn debugger_stops_at_an_opcode_breakpoint() {
+            let mut ram = crate::bus::FlatRam::default();
+            ram.0[1] = 0x76; // HALT, the opcode we'll break on
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            let mut cpu = SM83::default();
+            let mut debugger = Debugger::default();
+            debugger.break_on_opcode(0x76);
+
+            let reason = debugger.run_until(&mut cpu, &mut mb, 0x0002, 10);
+
+            assert_eq!(reason, StopReason::HitOpcodeBreakpoint);
+            assert_eq!(cpu.registers.pc, 0x0001);
+        }
+
+        #[test]
+        fn debugger_reads_and_writes_registers_and_memory() {
+            let mut mb = Motherboard::default();
+            let mut cpu = SM83::default();
+            let debugger = Debugger::default();
+
+            debugger.write_register8(&mut cpu, Reg8::A, 0x7B);
+            debugger.write_register16(&mut cpu, Reg16::HL, 0xC000);
+            debugger.write_memory(&mut mb, 0xC000, 0x42);
+
+            assert_eq!(debugger.read_register8(&cpu, Reg8::A), 0x7B);
+            assert_eq!(debugger.read_register16(&cpu, Reg16::HL), 0xC000);
+            assert_eq!(debugger.read_memory(&mb, 0xC000), 0x42);
+        }
+
+        #[test]
+        fn debugger_disassembles_the_next_instruction() {
+            let ram = crate::bus::FlatRam::default();
+            let mut mb = Motherboard {
+                cpu: SM83::default(),
+                memory_map: Box::new(ram),
+            };
+            mb.memory_map.write_byte(0x0000, 0x3E); // LD A,n
+            mb.memory_map.write_byte(0x0001, 0x7B);
+            let cpu = SM83::default();
+            let debugger = Debugger::default();
+
+            let (mnemonic, length) = debugger.disassemble_next(&cpu, &mb);
+
+            assert_eq!(mnemonic, "LD A,0x7B");
+            assert_eq!(length, 2);
+        }
+
+        #[test]
+        fn debugger_dumps_registers_as_a_one_line_trace() {
+            let mut cpu = SM83::default();
+            cpu.registers.a = 0x01;
+            cpu.registers.f.insert(Flags::ZERO);
+            cpu.registers.write16(Reg16::BC, 0x0013);
+            cpu.registers.pc = 0x0100;
+            let debugger = Debugger::default();
+
+            let trace = debugger.dump_registers(&cpu);
+
+            assert_eq!(trace, "A=01 F=80 [Z---] BC=0013 DE=0000 HL=0000 SP=0000 PC=0100");
+        }
     }
 }
@@ -0,0 +1,541 @@
+use crate::bus::{MemoryInterface, CYCLES_PER_M_CYCLE};
+use crate::timer::{MmioDevice, Timer};
+use crate::Address;
+
+/// 0xFF04..=0xFF07: DIV/TIMA/TMA/TAC, routed to the timer instead of flat
+/// I/O storage.
+const TIMER_RANGE: std::ops::RangeInclusive<Address> = 0xFF04..=0xFF07;
+
+/// Header offset of the cartridge type byte (selects the mapper).
+const CARTRIDGE_TYPE_OFFSET: usize = 0x0147;
+/// Header offset of the cartridge RAM size code.
+const RAM_SIZE_OFFSET: usize = 0x0149;
+const ROM_BANK_SIZE: usize = 0x4000;
+const RAM_BANK_SIZE: usize = 0x2000;
+
+#[derive(Clone, Copy)]
+enum MapperKind {
+    NoMbc,
+    Mbc1,
+    Mbc3,
+    Mbc5,
+}
+
+fn mapper_kind_for_cartridge_type(cartridge_type: u8) -> MapperKind {
+    match cartridge_type {
+        0x01..=0x03 => MapperKind::Mbc1,
+        0x0F..=0x13 => MapperKind::Mbc3,
+        0x19..=0x1E => MapperKind::Mbc5,
+        _ => MapperKind::NoMbc,
+    }
+}
+
+fn ram_bank_count_for_size_code(size_code: u8) -> usize {
+    match size_code {
+        0x02 => 1,
+        0x03 => 4,
+        0x04 => 16,
+        0x05 => 8,
+        _ => 0,
+    }
+}
+
+/// Per-mapper bank-switching state, written through the ROM address space
+/// (`0x0000..=0x7FFF`).
+enum Mapper {
+    NoMbc,
+    Mbc1 {
+        rom_bank_low5: u8,
+        bank_high2: u8,
+        ram_banking_mode: bool,
+        ram_enabled: bool,
+    },
+    Mbc3 {
+        rom_bank: u8,
+        ram_bank: u8,
+        ram_enabled: bool,
+    },
+    Mbc5 {
+        rom_bank: u16,
+        ram_bank: u8,
+        ram_enabled: bool,
+    },
+}
+
+impl Mapper {
+    fn new(kind: MapperKind) -> Self {
+        match kind {
+            MapperKind::NoMbc => Mapper::NoMbc,
+            MapperKind::Mbc1 => Mapper::Mbc1 {
+                rom_bank_low5: 1,
+                bank_high2: 0,
+                ram_banking_mode: false,
+                ram_enabled: false,
+            },
+            MapperKind::Mbc3 => Mapper::Mbc3 {
+                rom_bank: 1,
+                ram_bank: 0,
+                ram_enabled: false,
+            },
+            MapperKind::Mbc5 => Mapper::Mbc5 {
+                rom_bank: 1,
+                ram_bank: 0,
+                ram_enabled: false,
+            },
+        }
+    }
+
+    fn rom_bank(&self) -> usize {
+        match *self {
+            Mapper::NoMbc => 1,
+            Mapper::Mbc1 {
+                rom_bank_low5,
+                bank_high2,
+                ram_banking_mode,
+                ..
+            } => {
+                let bank = if ram_banking_mode {
+                    rom_bank_low5
+                } else {
+                    (bank_high2 << 5) | rom_bank_low5
+                };
+                bank as usize
+            }
+            Mapper::Mbc3 { rom_bank, .. } => rom_bank as usize,
+            Mapper::Mbc5 { rom_bank, .. } => rom_bank as usize,
+        }
+    }
+
+    fn ram_bank(&self) -> usize {
+        match *self {
+            Mapper::NoMbc => 0,
+            Mapper::Mbc1 {
+                bank_high2,
+                ram_banking_mode,
+                ..
+            } => {
+                if ram_banking_mode {
+                    bank_high2 as usize
+                } else {
+                    0
+                }
+            }
+            Mapper::Mbc3 { ram_bank, .. } => ram_bank as usize,
+            Mapper::Mbc5 { ram_bank, .. } => ram_bank as usize,
+        }
+    }
+
+    fn ram_enabled(&self) -> bool {
+        match *self {
+            Mapper::NoMbc => true,
+            Mapper::Mbc1 { ram_enabled, .. }
+            | Mapper::Mbc3 { ram_enabled, .. }
+            | Mapper::Mbc5 { ram_enabled, .. } => ram_enabled,
+        }
+    }
+
+    fn write_control(&mut self, address: Address, value: u8) {
+        match self {
+            Mapper::NoMbc => {}
+            Mapper::Mbc1 {
+                rom_bank_low5,
+                bank_high2,
+                ram_banking_mode,
+                ram_enabled,
+            } => match address {
+                0x0000..=0x1FFF => *ram_enabled = value & 0x0F == 0x0A,
+                0x2000..=0x3FFF => {
+                    let bank = value & 0x1F;
+                    *rom_bank_low5 = if bank == 0 { 1 } else { bank };
+                }
+                0x4000..=0x5FFF => *bank_high2 = value & 0x03,
+                0x6000..=0x7FFF => *ram_banking_mode = value & 0x01 != 0,
+                _ => unreachable!("address outside ROM space: {address:#06X}"),
+            },
+            Mapper::Mbc3 {
+                rom_bank,
+                ram_bank,
+                ram_enabled,
+            } => match address {
+                0x0000..=0x1FFF => *ram_enabled = value & 0x0F == 0x0A,
+                0x2000..=0x3FFF => {
+                    let bank = value & 0x7F;
+                    *rom_bank = if bank == 0 { 1 } else { bank };
+                }
+                // RTC register selection (0x08..=0x0C) is not modeled; only
+                // the RAM-bank half of this range is honoured.
+                0x4000..=0x5FFF => {
+                    if value <= 0x03 {
+                        *ram_bank = value;
+                    }
+                }
+                0x6000..=0x7FFF => {} // RTC latch: no-op, we have no RTC.
+                _ => unreachable!("address outside ROM space: {address:#06X}"),
+            },
+            Mapper::Mbc5 {
+                rom_bank,
+                ram_bank,
+                ram_enabled,
+            } => match address {
+                0x0000..=0x1FFF => *ram_enabled = value & 0x0F == 0x0A,
+                0x2000..=0x2FFF => *rom_bank = (*rom_bank & 0x100) | value as u16,
+                0x3000..=0x3FFF => *rom_bank = (*rom_bank & 0x0FF) | ((value as u16 & 0x01) << 8),
+                0x4000..=0x5FFF => *ram_bank = value & 0x0F,
+                _ => {} // 0x6000..=0x7FFF is unused by MBC5.
+            },
+        }
+    }
+}
+
+/// A loaded ROM image plus its mapper and external RAM.
+struct Cartridge {
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    mapper: Mapper,
+}
+
+impl Cartridge {
+    fn from_rom_bytes(rom: Vec<u8>) -> Self {
+        let cartridge_type = rom.get(CARTRIDGE_TYPE_OFFSET).copied().unwrap_or(0);
+        let ram_size = rom.get(RAM_SIZE_OFFSET).copied().unwrap_or(0);
+        let mapper = Mapper::new(mapper_kind_for_cartridge_type(cartridge_type));
+        let ram = vec![0; ram_bank_count_for_size_code(ram_size) * RAM_BANK_SIZE];
+        Self { rom, ram, mapper }
+    }
+
+    fn rom_bank_count(&self) -> usize {
+        (self.rom.len() / ROM_BANK_SIZE).max(1)
+    }
+
+    fn ram_bank_count(&self) -> usize {
+        (self.ram.len() / RAM_BANK_SIZE).max(1)
+    }
+
+    fn read_rom(&self, address: Address) -> u8 {
+        let offset = match address {
+            0x0000..=0x3FFF => address as usize,
+            0x4000..=0x7FFF => {
+                let bank = self.mapper.rom_bank() % self.rom_bank_count();
+                bank * ROM_BANK_SIZE + (address - 0x4000) as usize
+            }
+            _ => unreachable!("address outside ROM space: {address:#06X}"),
+        };
+        self.rom.get(offset).copied().unwrap_or(0xFF)
+    }
+
+    fn write_rom(&mut self, address: Address, value: u8) {
+        self.mapper.write_control(address, value);
+    }
+
+    fn read_ram(&self, address: Address) -> u8 {
+        if self.ram.is_empty() || !self.mapper.ram_enabled() {
+            return 0xFF;
+        }
+        let bank = self.mapper.ram_bank() % self.ram_bank_count();
+        let offset = bank * RAM_BANK_SIZE + (address - 0xA000) as usize;
+        self.ram.get(offset).copied().unwrap_or(0xFF)
+    }
+
+    fn write_ram(&mut self, address: Address, value: u8) {
+        if self.ram.is_empty() || !self.mapper.ram_enabled() {
+            return;
+        }
+        let bank = self.mapper.ram_bank() % self.ram_bank_count();
+        let offset = bank * RAM_BANK_SIZE + (address - 0xA000) as usize;
+        if let Some(byte) = self.ram.get_mut(offset) {
+            *byte = value;
+        }
+    }
+}
+
+/// The DMG's full 64 KiB address space: boot ROM overlay, cartridge ROM/RAM
+/// (routed through the detected mapper), VRAM, work RAM (with its echo),
+/// OAM, the I/O region, HRAM and the IE register.
+pub(crate) struct MemoryMap {
+    boot_rom: Option<Box<[u8; 0x100]>>,
+    boot_rom_mapped: bool,
+    cartridge: Cartridge,
+    vram: [u8; 0x2000],
+    work_ram: [u8; 0x2000],
+    oam: [u8; 0xA0],
+    io: [u8; 0x80],
+    hram: [u8; 0x7F],
+    ie: u8,
+    /// Devices routed to by address range instead of falling through to
+    /// flat I/O storage; checked in order, first range containing the
+    /// address wins. Just the timer for now, but adding PPU/APU/joypad
+    /// registers later is a matter of pushing another entry.
+    devices: Vec<(std::ops::RangeInclusive<Address>, Box<dyn MmioDevice>)>,
+}
+
+impl Default for MemoryMap {
+    fn default() -> Self {
+        Self::load_rom(Vec::new(), None, true)
+    }
+}
+
+impl MemoryMap {
+    /// Loads `rom` as the cartridge image, selecting a mapper from its
+    /// header. When `boot_rom` is given and `skip_boot_rom` is false,
+    /// `0x0000..=0x00FF` starts mapped to the boot ROM instead of the
+    /// cartridge; otherwise execution is expected to start post-boot at
+    /// `0x0100`.
+    pub(crate) fn load_rom(rom: Vec<u8>, boot_rom: Option<[u8; 0x100]>, skip_boot_rom: bool) -> Self {
+        let boot_rom_mapped = boot_rom.is_some() && !skip_boot_rom;
+        Self {
+            boot_rom: boot_rom.map(Box::new),
+            boot_rom_mapped,
+            cartridge: Cartridge::from_rom_bytes(rom),
+            vram: [0; 0x2000],
+            work_ram: [0; 0x2000],
+            oam: [0; 0xA0],
+            io: [0; 0x80],
+            hram: [0; 0x7F],
+            ie: 0,
+            devices: vec![(TIMER_RANGE, Box::new(Timer::default()))],
+        }
+    }
+
+    /// Returns the registered device claiming `address`, if any.
+    fn device_at(&self, address: Address) -> Option<&dyn MmioDevice> {
+        self.devices
+            .iter()
+            .find(|(range, _)| range.contains(&address))
+            .map(|(_, device)| device.as_ref())
+    }
+
+    /// Returns the registered device claiming `address`, if any.
+    fn device_at_mut(&mut self, address: Address) -> Option<&mut (dyn MmioDevice + '_)> {
+        for (range, device) in &mut self.devices {
+            if range.contains(&address) {
+                return Some(device.as_mut());
+            }
+        }
+        None
+    }
+
+    pub(crate) fn boot_rom_mapped(&self) -> bool {
+        self.boot_rom_mapped
+    }
+
+    pub(crate) fn read_byte_at(&self, address: Address) -> u8 {
+        if let Some(device) = self.device_at(address) {
+            return device.read(address);
+        }
+
+        match address {
+            0x0000..=0x00FF if self.boot_rom_mapped => {
+                self.boot_rom.as_ref().map_or(0xFF, |rom| rom[address as usize])
+            }
+            0x0000..=0x7FFF => self.cartridge.read_rom(address),
+            0x8000..=0x9FFF => self.vram[(address - 0x8000) as usize],
+            0xA000..=0xBFFF => self.cartridge.read_ram(address),
+            0xC000..=0xDFFF => self.work_ram[(address - 0xC000) as usize],
+            0xE000..=0xFDFF => self.work_ram[(address - 0xE000) as usize],
+            0xFE00..=0xFE9F => self.oam[(address - 0xFE00) as usize],
+            0xFEA0..=0xFEFF => 0xFF,
+            0xFF00..=0xFF7F => self.io[(address - 0xFF00) as usize],
+            0xFF80..=0xFFFE => self.hram[(address - 0xFF80) as usize],
+            0xFFFF => self.ie,
+        }
+    }
+
+    pub(crate) fn write_byte_at(&mut self, address: Address, data: u8) {
+        if let Some(device) = self.device_at_mut(address) {
+            device.write(address, data);
+            return;
+        }
+
+        match address {
+            0x0000..=0x7FFF => self.cartridge.write_rom(address, data),
+            0x8000..=0x9FFF => self.vram[(address - 0x8000) as usize] = data,
+            0xA000..=0xBFFF => self.cartridge.write_ram(address, data),
+            0xC000..=0xDFFF => self.work_ram[(address - 0xC000) as usize] = data,
+            0xE000..=0xFDFF => self.work_ram[(address - 0xE000) as usize] = data,
+            0xFE00..=0xFE9F => self.oam[(address - 0xFE00) as usize] = data,
+            0xFEA0..=0xFEFF => {}
+            // Writing a non-zero value here unmaps the boot ROM for good.
+            0xFF50 => {
+                self.io[(address - 0xFF00) as usize] = data;
+                if data != 0 {
+                    self.boot_rom_mapped = false;
+                }
+            }
+            0xFF00..=0xFF7F => self.io[(address - 0xFF00) as usize] = data,
+            0xFF80..=0xFFFE => self.hram[(address - 0xFF80) as usize] = data,
+            0xFFFF => self.ie = data,
+        }
+    }
+}
+
+impl MemoryInterface for MemoryMap {
+    fn read_byte(&mut self, address: Address) -> (u8, u8) {
+        (self.read_byte_at(address), CYCLES_PER_M_CYCLE)
+    }
+
+    fn write_byte(&mut self, address: Address, data: u8) -> u8 {
+        self.write_byte_at(address, data);
+        CYCLES_PER_M_CYCLE
+    }
+
+    fn interrupt_enable(&self) -> u8 {
+        self.ie
+    }
+
+    fn interrupt_flag(&self) -> u8 {
+        self.io[0x0F]
+    }
+
+    fn set_interrupt_flag(&mut self, flag: u8) {
+        self.io[0x0F] = flag;
+    }
+
+    fn tick(&mut self, cycles: u8) {
+        let mut irq = 0;
+        for (_, device) in &mut self.devices {
+            irq |= device.tick(cycles);
+        }
+        self.io[0x0F] |= irq;
+    }
+
+    fn peek_byte(&self, address: Address) -> u8 {
+        self.read_byte_at(address)
+    }
+
+    fn poke_byte(&mut self, address: Address, data: u8) {
+        self.write_byte_at(address, data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ROM of `bank_count` banks, with the header bytes selecting
+    /// `cartridge_type`/`ram_size_code` and each bank's first byte set to
+    /// its own bank number, so a bank switch is observable by reading
+    /// `0x4000` back.
+    fn banked_rom(cartridge_type: u8, ram_size_code: u8, bank_count: usize) -> Vec<u8> {
+        let mut rom = vec![0u8; ROM_BANK_SIZE * bank_count];
+        rom[CARTRIDGE_TYPE_OFFSET] = cartridge_type;
+        rom[RAM_SIZE_OFFSET] = ram_size_code;
+        for bank in 0..bank_count {
+            rom[bank * ROM_BANK_SIZE] = bank as u8;
+        }
+        rom
+    }
+
+    #[test]
+    fn mbc1_switches_the_high_rom_bank_on_a_0x2000_write() {
+        let rom = banked_rom(0x01, 0x00, 4);
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+        assert_eq!(mb.read_byte_at(0x4000), 1); // defaults to bank 1
+
+        mb.write_byte_at(0x2000, 3);
+
+        assert_eq!(mb.read_byte_at(0x4000), 3);
+    }
+
+    #[test]
+    fn mbc1_bank_select_0_is_forced_to_1() {
+        let rom = banked_rom(0x01, 0x00, 4);
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+        mb.write_byte_at(0x2000, 2);
+        assert_eq!(mb.read_byte_at(0x4000), 2);
+
+        mb.write_byte_at(0x2000, 0);
+
+        assert_eq!(mb.read_byte_at(0x4000), 1);
+    }
+
+    #[test]
+    fn mbc1_ram_banking_mode_moves_the_high_bits_from_rom_to_ram_bank() {
+        let rom = banked_rom(0x01, 0x03, 5); // MBC1, 4 RAM banks, 5 ROM banks
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+        mb.write_byte_at(0x0000, 0x0A); // enable RAM
+        mb.write_byte_at(0x2000, 0x01); // rom_bank_low5 = 1
+        mb.write_byte_at(0x4000, 0x01); // bank_high2 = 1
+
+        // Simple (ROM banking) mode: the high bits extend the ROM bank
+        // number: (1 << 5 | 1) % 5 == 3.
+        assert_eq!(mb.read_byte_at(0x4000), 3);
+
+        mb.write_byte_at(0x6000, 0x01); // switch to RAM banking mode
+        mb.write_byte_at(0xA000, 0x99);
+
+        assert_eq!(mb.read_byte_at(0xA000), 0x99); // landed in RAM bank 1
+        assert_eq!(mb.read_byte_at(0x4000), 1); // ROM bank is now just the low 5 bits
+    }
+
+    #[test]
+    fn mbc3_switches_the_rom_bank_on_a_0x2000_write() {
+        let rom = banked_rom(0x0F, 0x00, 4);
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+
+        mb.write_byte_at(0x2000, 3);
+
+        assert_eq!(mb.read_byte_at(0x4000), 3);
+    }
+
+    #[test]
+    fn mbc5_splits_the_rom_bank_number_across_two_registers() {
+        let rom = banked_rom(0x19, 0x00, 3);
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+
+        mb.write_byte_at(0x2000, 0x01); // bank[7:0] = 0x01
+        mb.write_byte_at(0x3000, 0x01); // bank[8] = 1, so bank = 0x101 = 257
+
+        assert_eq!(mb.read_byte_at(0x4000), (257 % 3) as u8);
+    }
+
+    #[test]
+    fn no_mbc_ignores_rom_bank_select_writes() {
+        let rom = banked_rom(0x00, 0x00, 2);
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+
+        mb.write_byte_at(0x2000, 1);
+
+        assert_eq!(mb.read_byte_at(0x4000), 1); // unchanged: still the fixed bank 1
+    }
+
+    #[test]
+    fn cartridge_ram_reads_as_0xff_until_enabled() {
+        let rom = banked_rom(0x01, 0x02, 2); // MBC1, 1 RAM bank
+        let mut mb = MemoryMap::load_rom(rom, None, true);
+        assert_eq!(mb.read_byte_at(0xA000), 0xFF);
+
+        mb.write_byte_at(0xA000, 0x42); // ignored while the gate is closed
+        assert_eq!(mb.read_byte_at(0xA000), 0xFF);
+
+        mb.write_byte_at(0x0000, 0x0A);
+        mb.write_byte_at(0xA000, 0x42);
+        assert_eq!(mb.read_byte_at(0xA000), 0x42);
+
+        mb.write_byte_at(0x0000, 0x00); // disable again
+        assert_eq!(mb.read_byte_at(0xA000), 0xFF); // gated, even though 0x42 is still stored
+    }
+
+    #[test]
+    fn echo_ram_aliases_work_ram() {
+        let mut mb = MemoryMap::default();
+
+        mb.write_byte_at(0xC010, 0x77);
+        assert_eq!(mb.read_byte_at(0xE010), 0x77);
+
+        mb.write_byte_at(0xE020, 0x55);
+        assert_eq!(mb.read_byte_at(0xC020), 0x55);
+    }
+
+    #[test]
+    fn writing_0xff50_unmaps_the_boot_rom() {
+        let boot_rom = [0xAA; 0x100];
+        let mut mb = MemoryMap::load_rom(vec![0x00; ROM_BANK_SIZE * 2], Some(boot_rom), false);
+        assert!(mb.boot_rom_mapped());
+        assert_eq!(mb.read_byte_at(0x0000), 0xAA);
+
+        mb.write_byte_at(0xFF50, 0x01);
+
+        assert!(!mb.boot_rom_mapped());
+        assert_eq!(mb.read_byte_at(0x0000), 0x00); // now falls through to cartridge ROM
+    }
+}
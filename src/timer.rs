@@ -0,0 +1,98 @@
+use crate::Address;
+
+/// T-cycles per TIMA increment for each of TAC's four clock selections
+/// (bits 0-1), indexed by that two-bit field.
+const TIMA_PERIODS: [u16; 4] = [1024, 16, 64, 256];
+
+/// TAC (0xFF07) bit enabling the TIMA clock.
+const TAC_ENABLE: u8 = 0x04;
+
+/// A memory-mapped device occupying a sub-range of the I/O space.
+/// [`MemoryMap`](crate::memory::MemoryMap) holds a registry of these,
+/// keyed by address range, and routes reads/writes/ticks to whichever one
+/// claims the address instead of falling through to flat I/O storage.
+pub(crate) trait MmioDevice {
+    fn read(&self, address: Address) -> u8;
+    fn write(&mut self, address: Address, data: u8);
+
+    /// Advances this device by `cycles` T-cycles, returning any IF bits it
+    /// wants OR'd in (0 if it requests nothing). Devices that aren't
+    /// clocked, or don't request interrupts, can leave this at its
+    /// default no-op.
+    fn tick(&mut self, cycles: u8) -> u8 {
+        let _ = cycles;
+        0
+    }
+}
+
+/// The DMG's timer: DIV (0xFF04), TIMA (0xFF05), TMA (0xFF06) and TAC
+/// (0xFF07).
+#[derive(Default)]
+pub(crate) struct Timer {
+    /// Internal 16-bit counter incremented every T-cycle; DIV is its high
+    /// byte, so DIV itself ticks at 4194304 / 256 = 16384 Hz. Writing DIV
+    /// resets this whole counter to 0.
+    divider: u16,
+    tima: u8,
+    tma: u8,
+    tac: u8,
+}
+
+/// IF (0xFF0F) bit the timer requests on TIMA overflow.
+const TIMER_INTERRUPT: u8 = 0x04;
+
+impl Timer {
+    fn enabled(&self) -> bool {
+        self.tac & TAC_ENABLE != 0
+    }
+
+    fn period(&self) -> u16 {
+        TIMA_PERIODS[(self.tac & 0x03) as usize]
+    }
+}
+
+impl MmioDevice for Timer {
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0xFF04 => (self.divider >> 8) as u8,
+            0xFF05 => self.tima,
+            0xFF06 => self.tma,
+            0xFF07 => self.tac,
+            _ => unreachable!("address outside the timer's range: {address:#06X}"),
+        }
+    }
+
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF04 => self.divider = 0,
+            0xFF05 => self.tima = data,
+            0xFF06 => self.tma = data,
+            0xFF07 => self.tac = data,
+            _ => unreachable!("address outside the timer's range: {address:#06X}"),
+        }
+    }
+
+    /// Advances the timer by `cycles` T-cycles, requesting the timer
+    /// interrupt for any TIMA overflow (already reloaded from TMA).
+    ///
+    /// This increments TIMA once every `period` T-cycles rather than
+    /// edge-detecting the selected divider bit, so it doesn't reproduce the
+    /// real hardware's TIMA-glitch-on-write quirk — just the documented
+    /// frequencies.
+    fn tick(&mut self, cycles: u8) -> u8 {
+        let mut irq = 0;
+        for _ in 0..cycles {
+            self.divider = self.divider.wrapping_add(1);
+            if self.enabled() && self.divider.is_multiple_of(self.period()) {
+                let (next, carried) = self.tima.overflowing_add(1);
+                if carried {
+                    self.tima = self.tma;
+                    irq |= TIMER_INTERRUPT;
+                } else {
+                    self.tima = next;
+                }
+            }
+        }
+        irq
+    }
+}